@@ -18,11 +18,13 @@ pub enum Request {
     },
     Relative {
         path: Pattern,
+        query: Pattern,
         force_in_context: bool,
     },
     Module {
         module: String,
         path: Pattern,
+        query: Pattern,
     },
     ServerRelative {
         path: Pattern,
@@ -36,7 +38,13 @@ pub enum Request {
     },
     Uri {
         protocol: String,
-        remainer: String,
+        remainer: Pattern,
+        query: Pattern,
+    },
+    DataUri {
+        mime_type: String,
+        encoding: String,
+        data: String,
     },
     Unknown {
         path: Pattern,
@@ -56,14 +64,16 @@ impl Request {
             } => format!("{path}"),
             Request::Relative {
                 path: Pattern::Constant(path),
+                query: Pattern::Constant(query),
                 ..
             } => {
-                format!("{path}")
+                format!("{path}{query}")
             }
             Request::Module {
                 module,
                 path: Pattern::Constant(path),
-            } => format!("{module}{path}"),
+                query: Pattern::Constant(query),
+            } => format!("{module}{path}{query}"),
             Request::ServerRelative {
                 path: Pattern::Constant(path),
             } => format!("{path}"),
@@ -74,7 +84,22 @@ impl Request {
             Request::PackageInternal {
                 path: Pattern::Constant(path),
             } => format!("{path}"),
-            Request::Uri { protocol, remainer } => format!("{protocol}{remainer}"),
+            Request::Uri {
+                protocol,
+                remainer: Pattern::Constant(remainer),
+                query: Pattern::Constant(query),
+            } => format!("{protocol}{remainer}{query}"),
+            Request::DataUri {
+                mime_type,
+                encoding,
+                data,
+            } => {
+                if encoding.is_empty() {
+                    format!("data:{mime_type},{data}")
+                } else {
+                    format!("data:{mime_type};{encoding},{data}")
+                }
+            }
             Request::Unknown {
                 path: Pattern::Constant(path),
             } => format!("{path}"),
@@ -94,8 +119,10 @@ impl Request {
                 } else if r.starts_with("#") {
                     Request::PackageInternal { path: request }
                 } else if r.starts_with("./") || r.starts_with("../") || r == "." || r == ".." {
+                    let (path, query) = split_query_fragment(r);
                     Request::Relative {
-                        path: request,
+                        path: Pattern::Constant(path),
+                        query,
                         force_in_context: false,
                     }
                 } else {
@@ -109,20 +136,36 @@ impl Request {
                     if WINDOWS_PATH.is_match(&r) {
                         return Request::Windows { path: request };
                     }
+                    if let Some(rest) = r.strip_prefix("data:") {
+                        if let Some((header, data)) = rest.split_once(',') {
+                            let (mime_type, encoding) = match header.strip_suffix(";base64") {
+                                Some(mime_type) => (mime_type.to_string(), "base64".to_string()),
+                                None => (header.to_string(), String::new()),
+                            };
+                            return Request::DataUri {
+                                mime_type,
+                                encoding,
+                                data: data.to_string(),
+                            };
+                        }
+                    }
                     if let Some(caps) = URI_PATH.captures(&r) {
                         if let (Some(protocol), Some(remainer)) = (caps.get(1), caps.get(2)) {
-                            // TODO data uri
+                            let (remainer, query) = split_query_fragment(remainer.as_str());
                             return Request::Uri {
                                 protocol: protocol.as_str().to_string(),
-                                remainer: remainer.as_str().to_string(),
+                                remainer: Pattern::Constant(remainer),
+                                query,
                             };
                         }
                     }
                     if let Some(caps) = MODULE_PATH.captures(&r) {
                         if let (Some(module), Some(path)) = (caps.get(1), caps.get(2)) {
+                            let (path, query) = split_query_fragment(path.as_str());
                             return Request::Module {
                                 module: module.as_str().to_string(),
-                                path: path.as_str().to_string().into(),
+                                path: Pattern::Constant(path),
+                                query,
                             };
                         }
                     }
@@ -137,11 +180,11 @@ impl Request {
                         Request::Raw { path, .. } => {
                             path.extend(iter);
                         }
-                        Request::Relative { path, .. } => {
-                            path.extend(iter);
+                        Request::Relative { path, query, .. } => {
+                            extend_with_query(path, query, iter);
                         }
-                        Request::Module { module: _, path } => {
-                            path.extend(iter);
+                        Request::Module { path, query, .. } => {
+                            extend_with_query(path, query, iter);
                         }
                         Request::ServerRelative { path } => {
                             path.extend(iter);
@@ -155,7 +198,12 @@ impl Request {
                         Request::PackageInternal { path } => {
                             path.extend(iter);
                         }
-                        Request::Uri { .. } => {
+                        Request::Uri {
+                            remainer, query, ..
+                        } => {
+                            extend_with_query(remainer, query, iter);
+                        }
+                        Request::DataUri { .. } => {
                             result = Request::Dynamic;
                         }
                         Request::Unknown { path } => {
@@ -179,6 +227,53 @@ impl Request {
     }
 }
 
+/// Splits a trailing `?query` and/or `#fragment` off of `s`, returning the part before it and a
+/// `Pattern` holding the rest (including the leading `?`/`#`), or an empty constant pattern when
+/// there isn't one.
+fn split_query_fragment(s: &str) -> (String, Pattern) {
+    match s.find(['?', '#']) {
+        Some(index) => (
+            s[..index].to_string(),
+            Pattern::Constant(s[index..].to_string()),
+        ),
+        None => (s.to_string(), Pattern::Constant(String::new())),
+    }
+}
+
+fn is_empty_pattern(pattern: &Pattern) -> bool {
+    matches!(pattern, Pattern::Constant(s) if s.is_empty())
+}
+
+/// Appends the remaining concatenation parts to `path`, unless/until one of them is a literal
+/// segment containing a `?`/`#`, at which point everything from there on (including the rest of
+/// `iter`) becomes `query` instead. This handles queries that only appear after an interpolation,
+/// e.g. `` `./locales/${lang}.json?raw` ``, which the first literal segment alone can't reveal.
+fn extend_with_query(
+    path: &mut Pattern,
+    query: &mut Pattern,
+    mut iter: impl Iterator<Item = Pattern>,
+) {
+    if !is_empty_pattern(query) {
+        query.extend(iter);
+        return;
+    }
+    while let Some(part) = iter.next() {
+        if let Pattern::Constant(s) = &part {
+            if let Some(index) = s.find(['?', '#']) {
+                let before = s[..index].to_string();
+                let after = s[index..].to_string();
+                if !before.is_empty() {
+                    path.extend(std::iter::once(Pattern::Constant(before)));
+                }
+                *query = Pattern::Constant(after);
+                query.extend(iter);
+                return;
+            }
+        }
+        path.extend(std::iter::once(part));
+    }
+}
+
 #[turbo_tasks::value_impl]
 impl RequestVc {
     #[turbo_tasks::function]
@@ -203,6 +298,7 @@ impl RequestVc {
     pub fn relative(request: Value<Pattern>, force_in_context: bool) -> Self {
         Self::slot(Request::Relative {
             path: request.into_value(),
+            query: Pattern::Constant(String::new()),
             force_in_context,
         })
     }
@@ -212,6 +308,7 @@ impl RequestVc {
         Self::slot(Request::Module {
             module,
             path: path.into_value(),
+            query: Pattern::Constant(String::new()),
         })
     }
 }
@@ -233,26 +330,46 @@ impl ValueToString for Request {
             }
             Request::Relative {
                 path,
+                query,
                 force_in_context,
             } => {
                 if *force_in_context {
-                    format!("relative-in-context {path}")
+                    format!("relative-in-context {path}{query}")
                 } else {
-                    format!("relative {path}")
+                    format!("relative {path}{query}")
                 }
             }
-            Request::Module { module, path } => {
+            Request::Module {
+                module,
+                path,
+                query,
+            } => {
                 if path.could_match_others("") {
-                    format!("module \"{module}\" with subpath {path}")
+                    format!("module \"{module}\" with subpath {path}{query}")
                 } else {
-                    format!("module \"{module}\"")
+                    format!("module \"{module}\"{query}")
                 }
             }
             Request::ServerRelative { path } => format!("server relative {path}"),
             Request::Windows { path } => format!("windows {path}"),
             Request::Empty => format!("empty"),
             Request::PackageInternal { path } => format!("package internal {path}"),
-            Request::Uri { protocol, remainer } => format!("uri \"{protocol}\" \"{remainer}\""),
+            Request::Uri {
+                protocol,
+                remainer,
+                query,
+            } => format!("uri \"{protocol}\" \"{remainer}\"{query}"),
+            Request::DataUri {
+                mime_type,
+                encoding,
+                ..
+            } => {
+                if encoding.is_empty() {
+                    format!("data uri \"{mime_type}\"")
+                } else {
+                    format!("data uri \"{mime_type}\" ({encoding})")
+                }
+            }
             Request::Unknown { path } => format!("unknown {path}"),
             Request::Dynamic => format!("dynamic"),
             Request::Alternatives { requests } => format!(
@@ -0,0 +1,78 @@
+use std::{collections::HashMap, fs::File, io::Write, path::Path};
+
+use serde_json::json;
+
+use crate::store_container::StoreContainer;
+
+/// Writes the current contents of `store` as a Chrome/Perfetto Trace Event Format JSON file, so a
+/// captured Turbopack trace can be opened directly in `chrome://tracing` or the Perfetto UI.
+///
+/// Timestamps in the store are nanoseconds; the Trace Event Format wants microseconds, so every
+/// `ts`/`dur` is divided by 1000. A span is emitted as one `"X"` event per self-time stretch it
+/// recorded (see [`crate::span::SelfTimeSpan`]), since an async span can be polled on different
+/// executor threads over its lifetime. Span nesting is not shown in the output: a parent's self
+/// time stops where a child is entered and resumes where it exits, so on the same `tid` a
+/// parent's events sit beside its children's rather than enclosing them. A span with no recorded
+/// self time (no matching `Enter`/`Exit` rows arrived for it) falls back to its full
+/// start/duration on a synthetic "unknown thread" id of 0 rather than being dropped from the
+/// export.
+pub fn export_trace(store: &StoreContainer, path: &Path) -> std::io::Result<()> {
+    let store = store.read();
+
+    let mut trace_events = Vec::new();
+    let mut thread_names = HashMap::new();
+
+    for span in store.spans() {
+        if span.self_time_spans().is_empty() {
+            thread_names
+                .entry(0)
+                .or_insert_with(|| "unknown thread".to_string());
+            trace_events.push(json!({
+                "name": span.name(),
+                "cat": span.target(),
+                "ph": "X",
+                "ts": span.start() as f64 / 1000.0,
+                "dur": span.duration() as f64 / 1000.0,
+                "pid": 1,
+                "tid": 0,
+                "args": span.values(),
+            }));
+            continue;
+        }
+
+        for self_time in span.self_time_spans() {
+            thread_names
+                .entry(self_time.thread_id)
+                .or_insert_with(|| format!("Thread {}", self_time.thread_id));
+
+            trace_events.push(json!({
+                "name": span.name(),
+                "cat": span.target(),
+                "ph": "X",
+                "ts": self_time.start as f64 / 1000.0,
+                "dur": (self_time.end - self_time.start) as f64 / 1000.0,
+                "pid": 1,
+                "tid": self_time.thread_id,
+                "args": span.values(),
+            }));
+        }
+    }
+
+    for (thread_id, name) in thread_names {
+        trace_events.push(json!({
+            "ph": "M",
+            "name": "thread_name",
+            "pid": 1,
+            "tid": thread_id,
+            "args": { "name": name },
+        }));
+    }
+
+    let trace = json!({
+        "traceEvents": trace_events,
+        "displayTimeUnit": "ns",
+    });
+
+    File::create(path)?.write_all(serde_json::to_vec(&trace)?.as_slice())?;
+    Ok(())
+}
@@ -1,9 +1,14 @@
 #![feature(iter_intersperse)]
 
-use std::{collections::HashSet, sync::Arc};
+use std::{path::PathBuf, sync::Arc, thread, time::Duration};
 
-use self::{reader::TraceReader, server::serve, store_container::StoreContainer};
+use self::{
+    reader::{TraceReader, TraceSource},
+    server::serve,
+    store_container::StoreContainer,
+};
 
+mod export;
 mod reader;
 mod server;
 mod span;
@@ -12,15 +17,44 @@ mod store_container;
 mod viewer;
 
 fn main() {
-    let args: HashSet<String> = std::env::args().skip(1).collect();
-
-    let arg = args
-        .iter()
-        .next()
-        .expect("missing argument: trace file path");
+    let mut args = std::env::args().skip(1);
+    let mut source_arg = None;
+    let mut export_path = None;
+    let mut framed = false;
+
+    while let Some(arg) = args.next() {
+        if arg == "--export" {
+            export_path = Some(PathBuf::from(
+                args.next().expect("--export requires a file path"),
+            ));
+        } else if arg == "--framed" {
+            framed = true;
+        } else if source_arg.is_none() {
+            source_arg = Some(arg);
+        }
+    }
+
+    let source_arg = source_arg
+        .expect("missing argument: trace file path, tcp://host:port, or unix:/path/to/socket");
+
+    let source = TraceSource::parse(&source_arg);
+    if export_path.is_some() && !matches!(source, TraceSource::File(_)) {
+        panic!(
+            "--export only supports a trace file source; a streaming source (tcp://, unix:) has \
+             no defined end, so there's no point at which a snapshot of it is complete"
+        );
+    }
 
     let store = Arc::new(StoreContainer::new());
-    let reader = TraceReader::spawn(store.clone(), arg.into());
+    let reader = TraceReader::spawn(store.clone(), source, framed);
+
+    if let Some(export_path) = export_path {
+        // Give the reader a moment to ingest everything that's already in the trace source
+        // before snapshotting it; this mirrors the reader's own poll interval for file sources.
+        thread::sleep(Duration::from_millis(500));
+        export::export_trace(&store, &export_path).expect("failed to export trace");
+        return;
+    }
 
     serve(store).unwrap();
 
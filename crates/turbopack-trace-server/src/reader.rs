@@ -1,7 +1,10 @@
+#[cfg(unix)]
+use std::os::unix::net::UnixListener;
 use std::{
     collections::{HashMap, HashSet},
     fs::File,
     io::Read,
+    net::{SocketAddr, TcpListener},
     path::PathBuf,
     sync::Arc,
     thread::{self, JoinHandle},
@@ -17,28 +20,152 @@ use crate::{
 
 const MAX_ROWS_PER_LOCK: usize = 100 * 1024;
 
+/// Upper bound on a framed row's declared length. Guards against a corrupted length prefix (e.g.
+/// a bit flip) turning into a huge value that would otherwise make the reader wait forever for
+/// bytes that will never arrive.
+const MAX_FRAME_SIZE: usize = 16 * 1024 * 1024;
+
+/// Where a [`TraceReader`] consumes its postcard-encoded [`TraceRow`] stream from.
+pub enum TraceSource {
+    /// Poll a trace file on disk, e.g. written by a process that's still running.
+    File(PathBuf),
+    /// Accept connections on a TCP socket and stream rows live, e.g. `tcp://127.0.0.1:1234`.
+    Tcp(SocketAddr),
+    /// Accept connections on a Unix domain socket and stream rows live, e.g.
+    /// `unix:/tmp/turbopack.trace.sock`.
+    #[cfg(unix)]
+    UnixSocket(PathBuf),
+}
+
+impl TraceSource {
+    pub fn parse(arg: &str) -> Self {
+        if let Some(addr) = arg.strip_prefix("tcp://") {
+            TraceSource::Tcp(
+                addr.parse()
+                    .unwrap_or_else(|_| panic!("invalid tcp address: {addr}")),
+            )
+        } else if let Some(path) = arg.strip_prefix("unix:") {
+            #[cfg(unix)]
+            {
+                TraceSource::UnixSocket(path.into())
+            }
+            #[cfg(not(unix))]
+            {
+                panic!("unix sockets are not supported on this platform: {path}")
+            }
+        } else {
+            TraceSource::File(arg.into())
+        }
+    }
+}
+
 pub struct TraceReader {
     store: Arc<StoreContainer>,
-    path: PathBuf,
+    source: TraceSource,
+    /// Whether each `TraceRow` is prefixed with a little-endian `u32` byte count. Framed streams
+    /// can resync to the next frame after a corrupt one instead of scanning byte-by-byte.
+    framed: bool,
 }
 
 impl TraceReader {
-    pub fn spawn(store: Arc<StoreContainer>, path: PathBuf) -> JoinHandle<()> {
-        let mut reader = Self { store, path };
+    pub fn spawn(store: Arc<StoreContainer>, source: TraceSource, framed: bool) -> JoinHandle<()> {
+        let mut reader = Self {
+            store,
+            source,
+            framed,
+        };
         std::thread::spawn(move || reader.run())
     }
 
     pub fn run(&mut self) {
+        match &self.source {
+            TraceSource::File(path) => self.run_file(path.clone()),
+            TraceSource::Tcp(addr) => self.run_tcp(*addr),
+            #[cfg(unix)]
+            TraceSource::UnixSocket(path) => self.run_unix_socket(path.clone()),
+        }
+    }
+
+    fn run_file(&mut self, path: PathBuf) {
         loop {
-            if self.try_read() {
+            if self.try_read(&path) {
                 self.store.write().reset();
             }
             thread::sleep(Duration::from_millis(500));
         }
     }
 
-    fn try_read(&mut self) -> bool {
-        let Ok(mut file) = File::open(&self.path) else {
+    fn run_tcp(&mut self, addr: SocketAddr) {
+        let listener = TcpListener::bind(addr).expect("failed to bind trace socket");
+        println!("listening for trace connections on {addr}");
+        loop {
+            match listener.accept() {
+                Ok((stream, _)) => self.consume_stream(stream),
+                Err(err) => println!("error accepting trace connection: {err:?}"),
+            }
+        }
+    }
+
+    #[cfg(unix)]
+    fn run_unix_socket(&mut self, path: PathBuf) {
+        // A stale socket file from a previous run would otherwise make bind fail.
+        let _ = std::fs::remove_file(&path);
+        let listener = UnixListener::bind(&path).expect("failed to bind trace socket");
+        println!("listening for trace connections on {}", path.display());
+        loop {
+            match listener.accept() {
+                Ok((stream, _)) => self.consume_stream(stream),
+                Err(err) => println!("error accepting trace connection: {err:?}"),
+            }
+        }
+    }
+
+    /// Consumes a single live connection until the writer closes it, resetting the store at the
+    /// start of each new connection. This is a proxy for "the trace stream restarted", not a true
+    /// restart signal: `TraceRow` has no explicit restart marker to key off of, so a benign
+    /// reconnect (network blip, proxy timeout) looks identical to a real restart and will also
+    /// wipe prior history. Accepted for now for long-running streaming sources; revisit if
+    /// `TraceRow` grows a real marker.
+    fn consume_stream(&mut self, mut stream: impl Read) {
+        self.store.write().reset();
+
+        let mut reader_state = ReaderState::default();
+        let mut buffer = Vec::new();
+        let mut index = 0;
+
+        loop {
+            let mut chunk = [0; 1024 * 1024];
+            match stream.read(&mut chunk) {
+                Ok(0) => {
+                    // The writer closed the connection, wait for the next one.
+                    return;
+                }
+                Ok(bytes_read) => {
+                    if index > 0 && buffer.len() + bytes_read > buffer.capacity() {
+                        buffer.splice(..index, std::iter::empty());
+                        index = 0;
+                    }
+                    buffer.extend_from_slice(&chunk[..bytes_read]);
+                    let (rows, dropped_bytes) = decode_rows(&buffer, &mut index, self.framed);
+                    if !rows.is_empty() || dropped_bytes > 0 {
+                        let mut store = self.store.write();
+                        for row in rows {
+                            process(&mut store, &mut reader_state, row);
+                        }
+                        store.invalidate_outdated_spans(&reader_state.outdated_spans);
+                        reader_state.outdated_spans.clear();
+                        if dropped_bytes > 0 {
+                            store.report_dropped_bytes(dropped_bytes);
+                        }
+                    }
+                }
+                Err(_) => return,
+            }
+        }
+    }
+
+    fn try_read(&mut self, path: &PathBuf) -> bool {
+        let Ok(mut file) = File::open(path) else {
             return false;
         };
 
@@ -63,22 +190,8 @@ impl TraceReader {
                             index = 0;
                         }
                         buffer.extend_from_slice(&chunk[..bytes_read]);
-                        let mut rows = Vec::new();
-                        loop {
-                            match postcard::take_from_bytes(&buffer[index..]) {
-                                Ok((row, remaining)) => {
-                                    index = buffer.len() - remaining.len();
-                                    rows.push(row);
-                                }
-                                Err(err) => {
-                                    if matches!(err, postcard::Error::DeserializeUnexpectedEnd) {
-                                        break;
-                                    }
-                                    println!("error: {:?}", err);
-                                }
-                            }
-                        }
-                        if !rows.is_empty() {
+                        let (rows, dropped_bytes) = decode_rows(&buffer, &mut index, self.framed);
+                        if !rows.is_empty() || dropped_bytes > 0 {
                             let mut store = self.store.write();
                             total_rows += rows.len();
                             for row in rows {
@@ -86,6 +199,9 @@ impl TraceReader {
                             }
                             store.invalidate_outdated_spans(&reader_state.outdated_spans);
                             reader_state.outdated_spans.clear();
+                            if dropped_bytes > 0 {
+                                store.report_dropped_bytes(dropped_bytes);
+                            }
                         }
                     }
                 }
@@ -100,6 +216,69 @@ impl TraceReader {
     }
 }
 
+/// Decodes as many complete `TraceRow`s as are currently buffered, advancing `index` past
+/// everything consumed (including any bytes dropped to recover from corruption) and returning the
+/// decoded rows plus the number of bytes that had to be dropped to get there.
+///
+/// In framed mode each row is prefixed with a little-endian `u32` byte count, so a corrupt frame
+/// can be skipped in one jump using its own declared length. Unframed streams have no such
+/// boundary, so recovery falls back to scanning forward one byte at a time until decoding
+/// succeeds again.
+fn decode_rows<'b>(
+    buffer: &'b [u8],
+    index: &mut usize,
+    framed: bool,
+) -> (Vec<TraceRow<'b>>, usize) {
+    let mut rows = Vec::new();
+    let mut dropped_bytes = 0;
+    loop {
+        if framed {
+            let available = &buffer[*index..];
+            if available.len() < 4 {
+                break;
+            }
+            let len = u32::from_le_bytes(available[..4].try_into().unwrap()) as usize;
+            if len > MAX_FRAME_SIZE {
+                // The length prefix itself is almost certainly corrupt rather than describing a
+                // real frame; there's no valid boundary to skip to, so resync byte-by-byte like
+                // the unframed path does.
+                println!("dropping corrupt frame length ({len} bytes), resyncing");
+                *index += 1;
+                dropped_bytes += 1;
+                continue;
+            }
+            if available.len() < 4 + len {
+                break;
+            }
+            match postcard::from_bytes(&available[4..4 + len]) {
+                Ok(row) => rows.push(row),
+                Err(err) => {
+                    println!("dropping corrupt frame ({len} bytes): {:?}", err);
+                    dropped_bytes += 4 + len;
+                }
+            }
+            *index += 4 + len;
+        } else {
+            match postcard::take_from_bytes(&buffer[*index..]) {
+                Ok((row, remaining)) => {
+                    *index = buffer.len() - remaining.len();
+                    rows.push(row);
+                }
+                Err(err) => {
+                    if matches!(err, postcard::Error::DeserializeUnexpectedEnd) {
+                        break;
+                    }
+                    // No frame boundary to skip to, resync byte-by-byte instead.
+                    println!("error: {:?}", err);
+                    *index += 1;
+                    dropped_bytes += 1;
+                }
+            }
+        }
+    }
+    (rows, dropped_bytes)
+}
+
 fn process(store: &mut StoreWriteGuard, state: &mut ReaderState, row: TraceRow<'_>) {
     match row {
         TraceRow::Start {
@@ -163,7 +342,13 @@ fn process(store: &mut StoreWriteGuard, state: &mut ReaderState, row: TraceRow<'
             let stack = state.thread_stacks.entry(thread_id).or_default();
             if let Some(&parent) = stack.last() {
                 if let Some(parent_start) = state.self_time_started.remove(&(parent, thread_id)) {
-                    store.add_self_time(parent, parent_start, ts, &mut state.outdated_spans);
+                    store.add_self_time(
+                        parent,
+                        thread_id,
+                        parent_start,
+                        ts,
+                        &mut state.outdated_spans,
+                    );
                 }
             }
             stack.push(id);
@@ -188,11 +373,11 @@ fn process(store: &mut StoreWriteGuard, state: &mut ReaderState, row: TraceRow<'
                 }
             }
             if let Some(start) = state.self_time_started.remove(&(id, thread_id)) {
-                store.add_self_time(id, start, ts, &mut state.outdated_spans);
+                store.add_self_time(id, thread_id, start, ts, &mut state.outdated_spans);
             }
         }
         TraceRow::Event { ts, parent, values } => {
-            let _parent = if let Some(parent) = parent {
+            let parent = if let Some(parent) = parent {
                 if let Some(parent) = state.active_ids.get(&parent) {
                     Some(*parent)
                 } else {
@@ -213,6 +398,15 @@ fn process(store: &mut StoreWriteGuard, state: &mut ReaderState, row: TraceRow<'
             } else {
                 None
             };
+            store.add_event(
+                parent,
+                ts,
+                values
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), v.to_string()))
+                    .collect(),
+                &mut state.outdated_spans,
+            );
         }
     }
 }
@@ -0,0 +1,70 @@
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::TcpListener,
+    sync::Arc,
+};
+
+use crate::{
+    store_container::StoreContainer,
+    viewer::{render_spans, INDEX_HTML},
+};
+
+/// Serves the trace viewer over plain HTTP: `GET /` returns the viewer page, `GET /api/spans`
+/// returns the current store contents as JSON (see [`render_spans`]), polled by that page.
+/// Anything else gets a 404. One connection is handled at a time, with no keep-alive, which is
+/// fine for the low request rate a local polling viewer generates.
+pub fn serve(store: Arc<StoreContainer>) -> std::io::Result<()> {
+    let listener = TcpListener::bind("127.0.0.1:7380")?;
+    println!(
+        "trace viewer listening on http://{}",
+        listener.local_addr()?
+    );
+
+    for stream in listener.incoming() {
+        let mut stream = match stream {
+            Ok(stream) => stream,
+            Err(err) => {
+                println!("error accepting viewer connection: {err:?}");
+                continue;
+            }
+        };
+
+        let mut request_line = String::new();
+        if BufReader::new(&stream)
+            .read_line(&mut request_line)
+            .is_err()
+        {
+            continue;
+        }
+        let path = request_line
+            .split_whitespace()
+            .nth(1)
+            .unwrap_or("")
+            .to_string();
+
+        let (content_type, body) = match path.as_str() {
+            "/" => ("text/html", INDEX_HTML.to_string()),
+            "/api/spans" => ("application/json", render_spans(&store.read())),
+            _ => {
+                if let Err(err) =
+                    stream.write_all(b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n")
+                {
+                    println!("error writing viewer response: {err:?}");
+                }
+                continue;
+            }
+        };
+
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: {content_type}\r\nContent-Length: \
+             {}\r\nAccess-Control-Allow-Origin: *\r\n\r\n{}",
+            body.len(),
+            body
+        );
+        if let Err(err) = stream.write_all(response.as_bytes()) {
+            println!("error writing viewer response: {err:?}");
+        }
+    }
+
+    Ok(())
+}
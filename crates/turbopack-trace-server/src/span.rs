@@ -0,0 +1,69 @@
+use std::collections::HashMap;
+
+/// Identifies a [`Span`] within a [`crate::store::Store`]. Stable for the lifetime of the store,
+/// reused only after a `reset()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SpanId(pub(crate) usize);
+
+/// A point-in-time marker (log line, cache hit, etc.) attached to the span it occurred in.
+#[derive(Debug, Clone)]
+pub struct SpanEvent {
+    pub ts: u64,
+    pub values: HashMap<String, String>,
+}
+
+/// A contiguous stretch of self time the span spent running on a single OS thread, as recorded by
+/// a matching `Enter`/`Exit` pair. A span can have several of these across different threads, e.g.
+/// an async task that gets polled on different executor worker threads.
+#[derive(Debug, Clone, Copy)]
+pub struct SelfTimeSpan {
+    pub thread_id: u64,
+    pub start: u64,
+    pub end: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct Span {
+    pub id: SpanId,
+    pub parent: Option<SpanId>,
+    pub name: String,
+    pub target: String,
+    pub start: u64,
+    pub end: u64,
+    pub self_time: u64,
+    pub self_time_spans: Vec<SelfTimeSpan>,
+    pub values: HashMap<String, String>,
+    pub events: Vec<SpanEvent>,
+}
+
+impl Span {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn target(&self) -> &str {
+        &self.target
+    }
+
+    pub fn start(&self) -> u64 {
+        self.start
+    }
+
+    pub fn duration(&self) -> u64 {
+        self.end.saturating_sub(self.start)
+    }
+
+    pub fn values(&self) -> &HashMap<String, String> {
+        &self.values
+    }
+
+    pub fn events(&self) -> &[SpanEvent] {
+        &self.events
+    }
+
+    /// The per-thread self-time stretches recorded for this span, in the order they occurred. A
+    /// span that was never entered (e.g. no matching `Enter`/`Exit` rows arrived) has none.
+    pub fn self_time_spans(&self) -> &[SelfTimeSpan] {
+        &self.self_time_spans
+    }
+}
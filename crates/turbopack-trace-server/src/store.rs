@@ -0,0 +1,118 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::span::{SelfTimeSpan, Span, SpanEvent, SpanId};
+
+/// Holds the spans parsed out of a trace so far, each with the events that occurred inside it; an
+/// event with no parent span is not kept. Lives behind a lock inside
+/// [`crate::store_container::StoreContainer`]; callers always go through
+/// [`crate::store_container::StoreWriteGuard`]/`StoreReadGuard`.
+#[derive(Default)]
+pub struct Store {
+    spans: Vec<Span>,
+    dropped_bytes: usize,
+    generation: u64,
+}
+
+impl Store {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn reset(&mut self) {
+        self.spans.clear();
+        self.dropped_bytes = 0;
+        self.generation += 1;
+    }
+
+    pub fn spans(&self) -> impl Iterator<Item = &Span> {
+        self.spans.iter()
+    }
+
+    pub fn span(&self, id: SpanId) -> Option<&Span> {
+        self.spans.get(id.0)
+    }
+
+    pub fn add_span(
+        &mut self,
+        parent: Option<SpanId>,
+        start: u64,
+        target: String,
+        name: String,
+        values: HashMap<String, String>,
+        outdated_spans: &mut HashSet<SpanId>,
+    ) -> SpanId {
+        let id = SpanId(self.spans.len());
+        self.spans.push(Span {
+            id,
+            parent,
+            name,
+            target,
+            start,
+            end: start,
+            self_time: 0,
+            self_time_spans: Vec::new(),
+            values,
+            events: Vec::new(),
+        });
+        if let Some(parent) = parent {
+            outdated_spans.insert(parent);
+        }
+        outdated_spans.insert(id);
+        id
+    }
+
+    pub fn add_self_time(
+        &mut self,
+        id: SpanId,
+        thread_id: u64,
+        start: u64,
+        end: u64,
+        outdated_spans: &mut HashSet<SpanId>,
+    ) {
+        if let Some(span) = self.spans.get_mut(id.0) {
+            span.self_time += end.saturating_sub(start);
+            span.self_time_spans.push(SelfTimeSpan {
+                thread_id,
+                start,
+                end,
+            });
+            span.end = span.end.max(end);
+            outdated_spans.insert(id);
+        }
+    }
+
+    pub fn add_event(
+        &mut self,
+        parent: Option<SpanId>,
+        ts: u64,
+        values: HashMap<String, String>,
+        outdated_spans: &mut HashSet<SpanId>,
+    ) {
+        let Some(parent) = parent else {
+            return;
+        };
+        if let Some(span) = self.spans.get_mut(parent.0) {
+            span.events.push(SpanEvent { ts, values });
+            span.end = span.end.max(ts);
+            outdated_spans.insert(parent);
+        }
+    }
+
+    pub fn invalidate_outdated_spans(&mut self, outdated_spans: &HashSet<SpanId>) {
+        if !outdated_spans.is_empty() {
+            self.generation += 1;
+        }
+    }
+
+    pub fn report_dropped_bytes(&mut self, dropped_bytes: usize) {
+        self.dropped_bytes += dropped_bytes;
+    }
+
+    pub fn dropped_bytes(&self) -> usize {
+        self.dropped_bytes
+    }
+
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+}
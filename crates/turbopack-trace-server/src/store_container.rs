@@ -0,0 +1,111 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{RwLock, RwLockReadGuard, RwLockWriteGuard},
+};
+
+use crate::{
+    span::{Span, SpanId},
+    store::Store,
+};
+
+/// Shared, lock-guarded handle to the [`Store`]. Cloned into the reader thread and the HTTP
+/// server so both can access the same trace data.
+#[derive(Default)]
+pub struct StoreContainer {
+    store: RwLock<Store>,
+}
+
+impl StoreContainer {
+    pub fn new() -> Self {
+        Self {
+            store: RwLock::new(Store::new()),
+        }
+    }
+
+    pub fn read(&self) -> StoreReadGuard<'_> {
+        StoreReadGuard {
+            guard: self.store.read().unwrap(),
+        }
+    }
+
+    pub fn write(&self) -> StoreWriteGuard<'_> {
+        StoreWriteGuard {
+            guard: self.store.write().unwrap(),
+        }
+    }
+}
+
+pub struct StoreReadGuard<'a> {
+    guard: RwLockReadGuard<'a, Store>,
+}
+
+impl StoreReadGuard<'_> {
+    pub fn spans(&self) -> impl Iterator<Item = &Span> {
+        self.guard.spans()
+    }
+
+    pub fn span(&self, id: SpanId) -> Option<&Span> {
+        self.guard.span(id)
+    }
+
+    pub fn dropped_bytes(&self) -> usize {
+        self.guard.dropped_bytes()
+    }
+
+    pub fn generation(&self) -> u64 {
+        self.guard.generation()
+    }
+}
+
+pub struct StoreWriteGuard<'a> {
+    guard: RwLockWriteGuard<'a, Store>,
+}
+
+impl StoreWriteGuard<'_> {
+    pub fn reset(&mut self) {
+        self.guard.reset();
+    }
+
+    pub fn add_span(
+        &mut self,
+        parent: Option<SpanId>,
+        start: u64,
+        target: String,
+        name: String,
+        values: HashMap<String, String>,
+        outdated_spans: &mut HashSet<SpanId>,
+    ) -> SpanId {
+        self.guard
+            .add_span(parent, start, target, name, values, outdated_spans)
+    }
+
+    pub fn add_self_time(
+        &mut self,
+        id: SpanId,
+        thread_id: u64,
+        start: u64,
+        end: u64,
+        outdated_spans: &mut HashSet<SpanId>,
+    ) {
+        self.guard
+            .add_self_time(id, thread_id, start, end, outdated_spans);
+    }
+
+    pub fn add_event(
+        &mut self,
+        parent: Option<SpanId>,
+        ts: u64,
+        values: HashMap<String, String>,
+        outdated_spans: &mut HashSet<SpanId>,
+    ) {
+        self.guard.add_event(parent, ts, values, outdated_spans);
+    }
+
+    pub fn invalidate_outdated_spans(&mut self, outdated_spans: &HashSet<SpanId>) {
+        self.guard.invalidate_outdated_spans(outdated_spans);
+    }
+
+    pub fn report_dropped_bytes(&mut self, dropped_bytes: usize) {
+        self.guard.report_dropped_bytes(dropped_bytes);
+    }
+}
@@ -0,0 +1,59 @@
+use serde_json::json;
+
+use crate::store_container::StoreReadGuard;
+
+/// The HTML shell served at `/`. It polls `/api/spans` on an interval and renders spans as a flat
+/// list; this is intentionally minimal (no flame graph, no zoom/pan) until a richer viewer is
+/// worth building.
+pub const INDEX_HTML: &str = r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>Turbopack trace viewer</title>
+</head>
+<body>
+<pre id="spans">loading...</pre>
+<script>
+async function poll() {
+    const res = await fetch("/api/spans");
+    const data = await res.json();
+    document.getElementById("spans").textContent = JSON.stringify(data, null, 2);
+    setTimeout(poll, 1000);
+}
+poll();
+</script>
+</body>
+</html>
+"#;
+
+/// Renders the current store contents as the JSON payload served to the browser-based viewer:
+/// one entry per span, with its nested point-in-time events included so the UI can interleave
+/// markers with span timing instead of only showing durations.
+pub fn render_spans(store: &StoreReadGuard<'_>) -> String {
+    let spans: Vec<_> = store
+        .spans()
+        .map(|span| {
+            json!({
+                "id": span.id.0,
+                "parent": span.parent.map(|parent| parent.0),
+                "name": span.name(),
+                "target": span.target(),
+                "start": span.start(),
+                "duration": span.duration(),
+                "selfTime": span.self_time,
+                "values": span.values(),
+                "events": span.events().iter().map(|event| json!({
+                    "ts": event.ts,
+                    "values": event.values,
+                })).collect::<Vec<_>>(),
+            })
+        })
+        .collect();
+
+    serde_json::to_string(&json!({
+        "spans": spans,
+        "droppedBytes": store.dropped_bytes(),
+        "generation": store.generation(),
+    }))
+    .unwrap_or_else(|_| "{}".to_string())
+}